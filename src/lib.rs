@@ -2,7 +2,11 @@ mod utils;
 
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
-use kmeans_colors::get_kmeans;
+use kmeans_colors::{get_kmeans, Calculate, Kmeans};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
 use utils::set_panic_hook;
 
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
@@ -62,6 +66,20 @@ impl Color {
     pub fn to_rgb_string(&self) -> String {
         format!("rgb({}, {}, {})", self.r, self.g, self.b)
     }
+
+    /// Returns `[h, s, l]` with hue in degrees (0..360) and saturation/lightness on 0..1.
+    #[wasm_bindgen]
+    pub fn to_hsl(&self) -> Vec<f32> {
+        let (h, s, l) = rgb_to_hsl(self.r, self.g, self.b);
+        vec![h, s, l]
+    }
+
+    /// Returns `[h, s, v]` with hue in degrees (0..360) and saturation/value on 0..1.
+    #[wasm_bindgen]
+    pub fn to_hsv(&self) -> Vec<f32> {
+        let (h, s, v) = rgb_to_hsv(self.r, self.g, self.b);
+        vec![h, s, v]
+    }
 }
 
 // Palette result structure
@@ -98,6 +116,13 @@ impl PaletteResult {
     pub fn length(&self) -> usize {
         self.colors.len()
     }
+
+    /// Nearest named CSS/X11 color for each centroid, in the same order as `colors()`. A centroid
+    /// with no named color within the default distance threshold gets its hex string instead.
+    #[wasm_bindgen(getter)]
+    pub fn names(&self) -> Vec<String> {
+        self.colors.iter().map(name_color).collect()
+    }
 }
 
 // Simple RGB struct that implements the necessary traits for kmeans
@@ -185,12 +210,531 @@ impl kmeans_colors::Calculate for Rgb {
     }
 }
 
+// Which color space clustering is performed in
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSpace {
+    Rgb,
+    Lab,
+}
+
+// CIELAB point used for perceptual clustering
+#[derive(Debug, Clone, Copy, Default)]
+struct Lab {
+    l: f32,
+    a: f32,
+    b: f32,
+}
+
+impl Lab {
+    fn new(l: f32, a: f32, b: f32) -> Self {
+        Lab { l, a, b }
+    }
+}
+
+fn srgb_channel_to_linear(c: u8) -> f32 {
+    let c = c as f32 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_channel_to_srgb(c: f32) -> u8 {
+    let c = c.clamp(0.0, 1.0);
+    let c = if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+    (c.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+fn lab_f(t: f32) -> f32 {
+    if t > 0.008856 {
+        t.powf(1.0 / 3.0)
+    } else {
+        7.787 * t + 16.0 / 116.0
+    }
+}
+
+fn lab_f_inv(t: f32) -> f32 {
+    let t3 = t.powi(3);
+    if t3 > 0.008856 {
+        t3
+    } else {
+        (t - 16.0 / 116.0) / 7.787
+    }
+}
+
+// D65 reference white
+const LAB_XN: f32 = 0.95047;
+const LAB_YN: f32 = 1.0;
+const LAB_ZN: f32 = 1.08883;
+
+fn rgb_to_lab(r: u8, g: u8, b: u8) -> Lab {
+    let r_lin = srgb_channel_to_linear(r);
+    let g_lin = srgb_channel_to_linear(g);
+    let b_lin = srgb_channel_to_linear(b);
+
+    let x = 0.4124 * r_lin + 0.3576 * g_lin + 0.1805 * b_lin;
+    let y = 0.2126 * r_lin + 0.7152 * g_lin + 0.0722 * b_lin;
+    let z = 0.0193 * r_lin + 0.1192 * g_lin + 0.9505 * b_lin;
+
+    let fx = lab_f(x / LAB_XN);
+    let fy = lab_f(y / LAB_YN);
+    let fz = lab_f(z / LAB_ZN);
+
+    Lab::new(116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+}
+
+fn lab_to_rgb(lab: &Lab) -> (u8, u8, u8) {
+    let fy = (lab.l + 16.0) / 116.0;
+    let fx = fy + lab.a / 500.0;
+    let fz = fy - lab.b / 200.0;
+
+    let x = LAB_XN * lab_f_inv(fx);
+    let y = LAB_YN * lab_f_inv(fy);
+    let z = LAB_ZN * lab_f_inv(fz);
+
+    let r_lin = 3.2406 * x - 1.5372 * y - 0.4986 * z;
+    let g_lin = -0.9689 * x + 1.8758 * y + 0.0415 * z;
+    let b_lin = 0.0557 * x - 0.2040 * y + 1.0570 * z;
+
+    (
+        linear_channel_to_srgb(r_lin),
+        linear_channel_to_srgb(g_lin),
+        linear_channel_to_srgb(b_lin),
+    )
+}
+
+// CIEDE2000 color difference between two Lab points
+fn ciede2000(lab1: &Lab, lab2: &Lab) -> f32 {
+    let c1 = (lab1.a.powi(2) + lab1.b.powi(2)).sqrt();
+    let c2 = (lab2.a.powi(2) + lab2.b.powi(2)).sqrt();
+    let c_bar = (c1 + c2) / 2.0;
+
+    let g = 0.5 * (1.0 - (c_bar.powi(7) / (c_bar.powi(7) + 25f32.powi(7))).sqrt());
+    let a1_p = lab1.a * (1.0 + g);
+    let a2_p = lab2.a * (1.0 + g);
+
+    let c1_p = (a1_p.powi(2) + lab1.b.powi(2)).sqrt();
+    let c2_p = (a2_p.powi(2) + lab2.b.powi(2)).sqrt();
+
+    let h1_p = if a1_p == 0.0 && lab1.b == 0.0 {
+        0.0
+    } else {
+        lab1.b.atan2(a1_p).to_degrees().rem_euclid(360.0)
+    };
+    let h2_p = if a2_p == 0.0 && lab2.b == 0.0 {
+        0.0
+    } else {
+        lab2.b.atan2(a2_p).to_degrees().rem_euclid(360.0)
+    };
+
+    let delta_l_p = lab2.l - lab1.l;
+    let delta_c_p = c2_p - c1_p;
+
+    let delta_h_p = if c1_p * c2_p == 0.0 {
+        0.0
+    } else {
+        let mut diff = h2_p - h1_p;
+        if diff > 180.0 {
+            diff -= 360.0;
+        } else if diff < -180.0 {
+            diff += 360.0;
+        }
+        diff
+    };
+    let delta_h_p_big = 2.0 * (c1_p * c2_p).sqrt() * (delta_h_p.to_radians() / 2.0).sin();
+
+    let l_bar_p = (lab1.l + lab2.l) / 2.0;
+    let c_bar_p = (c1_p + c2_p) / 2.0;
+
+    let h_bar_p = if c1_p * c2_p == 0.0 {
+        h1_p + h2_p
+    } else if (h1_p - h2_p).abs() <= 180.0 {
+        (h1_p + h2_p) / 2.0
+    } else if h1_p + h2_p < 360.0 {
+        (h1_p + h2_p + 360.0) / 2.0
+    } else {
+        (h1_p + h2_p - 360.0) / 2.0
+    };
+
+    let t = 1.0 - 0.17 * (h_bar_p - 30.0).to_radians().cos()
+        + 0.24 * (2.0 * h_bar_p).to_radians().cos()
+        + 0.32 * (3.0 * h_bar_p + 6.0).to_radians().cos()
+        - 0.20 * (4.0 * h_bar_p - 63.0).to_radians().cos();
+
+    let delta_theta = 30.0 * (-(((h_bar_p - 275.0) / 25.0).powi(2))).exp();
+    let r_c = 2.0 * (c_bar_p.powi(7) / (c_bar_p.powi(7) + 25f32.powi(7))).sqrt();
+    let s_l = 1.0
+        + (0.015 * (l_bar_p - 50.0).powi(2)) / (20.0 + (l_bar_p - 50.0).powi(2)).sqrt();
+    let s_c = 1.0 + 0.045 * c_bar_p;
+    let s_h = 1.0 + 0.015 * c_bar_p * t;
+    let r_t = -r_c * (2.0 * delta_theta.to_radians()).sin();
+
+    let term_l = delta_l_p / s_l;
+    let term_c = delta_c_p / s_c;
+    let term_h = delta_h_p_big / s_h;
+
+    (term_l.powi(2) + term_c.powi(2) + term_h.powi(2) + r_t * term_c * term_h).sqrt()
+}
+
+impl kmeans_colors::Calculate for Lab {
+    fn get_closest_centroid(data: &[Self], centroids: &[Self], indices: &mut Vec<u8>) {
+        for color in data.iter() {
+            let mut closest_index = 0;
+            let mut min_distance = f32::MAX;
+
+            for (idx, centroid) in centroids.iter().enumerate() {
+                let distance = Self::difference(color, centroid);
+                if distance < min_distance {
+                    min_distance = distance;
+                    closest_index = idx;
+                }
+            }
+            indices.push(closest_index as u8);
+        }
+    }
+
+    fn recalculate_centroids(
+        _rng: &mut impl rand::Rng,
+        data: &[Self],
+        centroids: &mut [Self],
+        indices: &[u8],
+    ) {
+        for (idx, centroid) in centroids.iter_mut().enumerate() {
+            let mut sum_l = 0.0f32;
+            let mut sum_a = 0.0f32;
+            let mut sum_b = 0.0f32;
+            let mut count = 0u32;
+
+            for (&cluster_idx, color) in indices.iter().zip(data) {
+                if cluster_idx as usize == idx {
+                    sum_l += color.l;
+                    sum_a += color.a;
+                    sum_b += color.b;
+                    count += 1;
+                }
+            }
+
+            if count > 0 {
+                centroid.l = sum_l / count as f32;
+                centroid.a = sum_a / count as f32;
+                centroid.b = sum_b / count as f32;
+            }
+        }
+    }
+
+    fn check_loop(centroids: &[Self], old_centroids: &[Self]) -> f32 {
+        let mut total_diff = 0.0;
+        for (new, old) in centroids.iter().zip(old_centroids) {
+            total_diff += Self::difference(new, old);
+        }
+        total_diff
+    }
+
+    fn create_random(rng: &mut impl rand::Rng) -> Self {
+        Lab::new(
+            rng.gen_range(0.0..=100.0),
+            rng.gen_range(-128.0..=127.0),
+            rng.gen_range(-128.0..=127.0),
+        )
+    }
+
+    fn difference(c1: &Self, c2: &Self) -> f32 {
+        ciede2000(c1, c2)
+    }
+}
+
+// How initial centroids are chosen before the Lloyd iterations run
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InitMethod {
+    Random,
+    KMeansPlusPlus,
+}
+
+// Seed centroids from actual data points using D² weighting (k-means++)
+fn kmeans_plus_plus_init<C: Calculate + Clone>(data: &[C], k: usize, rng: &mut StdRng) -> Vec<C> {
+    let mut centroids: Vec<C> = Vec::with_capacity(k);
+    let first_idx = rng.gen_range(0..data.len());
+    centroids.push(data[first_idx].clone());
+
+    let mut min_dist_sq = vec![f32::MAX; data.len()];
+    while centroids.len() < k {
+        let last = centroids.last().unwrap();
+        for (point, dist_sq) in data.iter().zip(min_dist_sq.iter_mut()) {
+            let d = C::difference(point, last);
+            let d_sq = d * d;
+            if d_sq < *dist_sq {
+                *dist_sq = d_sq;
+            }
+        }
+
+        let next_idx = match WeightedIndex::new(min_dist_sq.iter()) {
+            Ok(weighted) => weighted.sample(rng),
+            // Every remaining point coincides with a chosen centroid; fall back to uniform.
+            Err(_) => rng.gen_range(0..data.len()),
+        };
+        centroids.push(data[next_idx].clone());
+    }
+
+    centroids
+}
+
+// Exposes a point's channels as floats so centroids can be recalculated as a weighted average
+trait WeightedChannels: Copy {
+    fn channels(&self) -> [f32; 3];
+    fn from_channels(channels: [f32; 3]) -> Self;
+}
+
+impl WeightedChannels for Rgb {
+    fn channels(&self) -> [f32; 3] {
+        [self.r as f32, self.g as f32, self.b as f32]
+    }
+
+    fn from_channels(channels: [f32; 3]) -> Self {
+        Rgb::new(
+            channels[0].round().clamp(0.0, 255.0) as u8,
+            channels[1].round().clamp(0.0, 255.0) as u8,
+            channels[2].round().clamp(0.0, 255.0) as u8,
+        )
+    }
+}
+
+impl WeightedChannels for Lab {
+    fn channels(&self) -> [f32; 3] {
+        [self.l, self.a, self.b]
+    }
+
+    fn from_channels(channels: [f32; 3]) -> Self {
+        Lab::new(channels[0], channels[1], channels[2])
+    }
+}
+
+// Recalculates centroids as `sum(weight * channel) / sum(weight)` instead of a plain average
+fn weighted_recalculate_centroids<C: WeightedChannels>(
+    data: &[C],
+    weights: &[f32],
+    centroids: &mut [C],
+    indices: &[u8],
+) {
+    for (idx, centroid) in centroids.iter_mut().enumerate() {
+        let mut sum = [0.0f32; 3];
+        let mut weight_total = 0.0f32;
+
+        for ((&cluster_idx, point), &weight) in indices.iter().zip(data).zip(weights) {
+            if cluster_idx as usize == idx {
+                let channels = point.channels();
+                sum[0] += weight * channels[0];
+                sum[1] += weight * channels[1];
+                sum[2] += weight * channels[2];
+                weight_total += weight;
+            }
+        }
+
+        if weight_total > 0.0 {
+            *centroid = C::from_channels([
+                sum[0] / weight_total,
+                sum[1] / weight_total,
+                sum[2] / weight_total,
+            ]);
+        }
+    }
+}
+
+// Bundles the Lloyd-iteration tuning knobs so `run_kmeans` doesn't need one parameter per field
+struct KmeansConfig {
+    max_iter: usize,
+    converge: f32,
+    verbose: bool,
+    seed: u64,
+    init: InitMethod,
+}
+
+// Runs Lloyd's algorithm to completion, optionally seeded with k-means++ centroids and/or
+// biased toward higher-weighted pixels during centroid recalculation
+fn run_kmeans<C: Calculate + Clone + WeightedChannels>(
+    k: usize,
+    data: &[C],
+    weights: Option<&[f32]>,
+    config: &KmeansConfig,
+) -> Kmeans<C> {
+    if weights.is_none() && config.init == InitMethod::Random {
+        return get_kmeans(k, config.max_iter, config.converge, config.verbose, data, config.seed);
+    }
+
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let mut centroids = match config.init {
+        InitMethod::KMeansPlusPlus => kmeans_plus_plus_init(data, k, &mut rng),
+        // Match get_kmeans' own behavior of seeding from real pixels rather than synthesizing
+        // channel values that may not correspond to anything in the image.
+        InitMethod::Random => (0..k).map(|_| data[rng.gen_range(0..data.len())].clone()).collect(),
+    };
+    let mut indices: Vec<u8> = Vec::with_capacity(data.len());
+    let mut iterations = 0;
+
+    let score = loop {
+        indices.clear();
+        C::get_closest_centroid(data, &centroids, &mut indices);
+
+        let old_centroids = centroids.clone();
+        match weights {
+            Some(w) => weighted_recalculate_centroids(data, w, &mut centroids, &indices),
+            None => C::recalculate_centroids(&mut rng, data, &mut centroids, &indices),
+        }
+
+        let diff = C::check_loop(&centroids, &old_centroids);
+        iterations += 1;
+
+        if config.verbose {
+            console_log!("k-means iteration {}: diff = {}", iterations, diff);
+        }
+
+        if diff <= config.converge || iterations >= config.max_iter {
+            break diff;
+        }
+    };
+
+    Kmeans {
+        score,
+        centroids,
+        indices,
+    }
+}
+
+// Converts an sRGB byte pixel to HSL, with hue in degrees (0..360) and saturation/lightness on 0..1
+fn rgb_to_hsl(r: u8, g: u8, b: u8) -> (f32, f32, f32) {
+    let rf = r as f32 / 255.0;
+    let gf = g as f32 / 255.0;
+    let bf = b as f32 / 255.0;
+
+    let max = rf.max(gf).max(bf);
+    let min = rf.min(gf).min(bf);
+    let delta = max - min;
+    let l = (max + min) / 2.0;
+
+    if delta == 0.0 {
+        return (0.0, 0.0, l);
+    }
+
+    let s = delta / (1.0 - (2.0 * l - 1.0).abs());
+
+    let mut h = if max == rf {
+        60.0 * (((gf - bf) / delta) % 6.0)
+    } else if max == gf {
+        60.0 * (((bf - rf) / delta) + 2.0)
+    } else {
+        60.0 * (((rf - gf) / delta) + 4.0)
+    };
+    if h < 0.0 {
+        h += 360.0;
+    }
+
+    (h, s, l)
+}
+
+// How much influence each pixel has on centroid recalculation
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeightMode {
+    Uniform,
+    Saturation,
+    Luminance,
+    Dominant,
+}
+
+// Smallest weight a pixel can carry; keeps a cluster's weight total from collapsing to zero
+const MIN_PIXEL_WEIGHT: f32 = 0.001;
+
+fn pixel_weight(rgb: &Rgb, mode: WeightMode) -> f32 {
+    let weight = match mode {
+        WeightMode::Uniform => 1.0,
+        WeightMode::Saturation => {
+            let (_, s, _) = rgb_to_hsl(rgb.r, rgb.g, rgb.b);
+            s
+        }
+        WeightMode::Luminance => {
+            let r = rgb.r as f32 / 255.0;
+            let g = rgb.g as f32 / 255.0;
+            let b = rgb.b as f32 / 255.0;
+            0.2126 * r + 0.7152 * g + 0.0722 * b
+        }
+        WeightMode::Dominant => {
+            // Triangular weight peaking at mid-tone lightness (L = 0.5)
+            let (_, _, l) = rgb_to_hsl(rgb.r, rgb.g, rgb.b);
+            1.0 - (2.0 * l - 1.0).abs()
+        }
+    };
+    weight.max(MIN_PIXEL_WEIGHT)
+}
+
+// Converts an sRGB byte pixel to HSV, with hue in degrees (0..360) and saturation/value on 0..1
+fn rgb_to_hsv(r: u8, g: u8, b: u8) -> (f32, f32, f32) {
+    let rf = r as f32 / 255.0;
+    let gf = g as f32 / 255.0;
+    let bf = b as f32 / 255.0;
+
+    let max = rf.max(gf).max(bf);
+    let min = rf.min(gf).min(bf);
+    let chroma = max - min;
+    let value = max;
+
+    let mut h = if chroma == 0.0 {
+        0.0
+    } else if max == rf {
+        60.0 * (((gf - bf) / chroma) % 6.0)
+    } else if max == gf {
+        60.0 * (((bf - rf) / chroma) + 2.0)
+    } else {
+        60.0 * (((rf - gf) / chroma) + 4.0)
+    };
+    if h < 0.0 {
+        h += 360.0;
+    }
+
+    let s = if value == 0.0 { 0.0 } else { chroma / value };
+
+    (h, s, value)
+}
+
+// Combines a pixel's quantized channels into a single 3D histogram bucket key
+fn quantize_key(r: u8, g: u8, b: u8, bits_per_channel: u8) -> u32 {
+    let shift = 8 - bits_per_channel;
+    let r_q = (r >> shift) as u32;
+    let g_q = (g >> shift) as u32;
+    let b_q = (b >> shift) as u32;
+    (r_q << (2 * bits_per_channel)) | (g_q << bits_per_channel) | b_q
+}
+
+// Reconstructs a representative sRGB color from a histogram bucket key
+fn bucket_to_rgb(key: u32, bits_per_channel: u8) -> (u8, u8, u8) {
+    let mask = (1u32 << bits_per_channel) - 1;
+    let b_q = key & mask;
+    let g_q = (key >> bits_per_channel) & mask;
+    let r_q = (key >> (2 * bits_per_channel)) & mask;
+
+    let max_level = mask as f32;
+    let scale = |level: u32| ((level as f32 / max_level) * 255.0).round() as u8;
+    (scale(r_q), scale(g_q), scale(b_q))
+}
+
 // Main palette extractor class
 #[wasm_bindgen]
 pub struct PaletteExtractor {
     max_iter: usize,
     converge: f32,
     verbose: bool,
+    color_space: ColorSpace,
+    init_method: InitMethod,
+    seed: u64,
+    lightness_lower: f32,
+    lightness_upper: f32,
+    filter_enabled: bool,
+    weight_mode: WeightMode,
 }
 
 #[wasm_bindgen]
@@ -198,11 +742,18 @@ impl PaletteExtractor {
     #[wasm_bindgen(constructor)]
     pub fn new() -> PaletteExtractor {
         set_panic_hook();
-        
+
         PaletteExtractor {
             max_iter: 20,
             converge: 5.0,
             verbose: false,
+            color_space: ColorSpace::Rgb,
+            init_method: InitMethod::Random,
+            seed: 0,
+            lightness_lower: 0.15,
+            lightness_upper: 0.85,
+            filter_enabled: false,
+            weight_mode: WeightMode::Uniform,
         }
     }
 
@@ -221,13 +772,44 @@ impl PaletteExtractor {
         self.verbose = verbose;
     }
 
+    #[wasm_bindgen]
+    pub fn set_color_space(&mut self, color_space: ColorSpace) {
+        self.color_space = color_space;
+    }
+
+    #[wasm_bindgen]
+    pub fn set_init(&mut self, init_method: InitMethod) {
+        self.init_method = init_method;
+    }
+
+    #[wasm_bindgen]
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    #[wasm_bindgen]
+    pub fn set_lightness_bounds(&mut self, lower: f32, upper: f32) {
+        self.lightness_lower = lower;
+        self.lightness_upper = upper;
+    }
+
+    #[wasm_bindgen]
+    pub fn set_filter_enabled(&mut self, enabled: bool) {
+        self.filter_enabled = enabled;
+    }
+
+    #[wasm_bindgen]
+    pub fn set_weight(&mut self, weight_mode: WeightMode) {
+        self.weight_mode = weight_mode;
+    }
+
     #[wasm_bindgen]
     pub fn extract_palette_from_pixels(
         &self, 
         pixels: &[u8], 
         k: usize
     ) -> Result<PaletteResult, JsValue> {
-        if pixels.len() % 4 != 0 {
+        if !pixels.len().is_multiple_of(4) {
             return Err(JsValue::from_str("Pixel data must be RGBA format (length divisible by 4)"));
         }
 
@@ -236,7 +818,7 @@ impl PaletteExtractor {
         }
 
         // Convert RGBA pixels to RGB pixels for kmeans
-        let rgb_pixels: Vec<Rgb> = pixels
+        let mut rgb_pixels: Vec<Rgb> = pixels
             .chunks_exact(4)
             .map(|rgba| Rgb::new(rgba[0], rgba[1], rgba[2])) // Skip alpha channel
             .collect();
@@ -245,40 +827,94 @@ impl PaletteExtractor {
             return Err(JsValue::from_str("No valid pixels found"));
         }
 
+        if self.filter_enabled {
+            let filtered: Vec<Rgb> = rgb_pixels
+                .iter()
+                .copied()
+                .filter(|color| {
+                    let (_, _, l) = rgb_to_hsl(color.r, color.g, color.b);
+                    l >= self.lightness_lower && l <= self.lightness_upper
+                })
+                .collect();
+
+            // If everything got filtered out (e.g. a flat-colored image), fall back to the
+            // unfiltered pixel set rather than erroring.
+            if !filtered.is_empty() {
+                rgb_pixels = filtered;
+            }
+        }
+
         if self.verbose {
             console_log!("Processing {} pixels for {} colors", rgb_pixels.len(), k);
         }
 
-        // Perform k-means clustering
-        let kmeans_result = get_kmeans(
-            k,
-            self.max_iter,
-            self.converge,
-            self.verbose,
-            &rgb_pixels,
-            0, // seed
-        );
+        let weights: Option<Vec<f32>> = if self.weight_mode == WeightMode::Uniform {
+            None
+        } else {
+            Some(
+                rgb_pixels
+                    .iter()
+                    .map(|rgb| pixel_weight(rgb, self.weight_mode))
+                    .collect(),
+            )
+        };
 
-        // Convert results to our format
-        let colors: Vec<Color> = kmeans_result
-            .centroids
-            .iter()
-            .map(|centroid| Color::new(centroid.r, centroid.g, centroid.b))
-            .collect();
+        let kmeans_config = KmeansConfig {
+            max_iter: self.max_iter,
+            converge: self.converge,
+            verbose: self.verbose,
+            seed: self.seed,
+            init: self.init_method,
+        };
 
-        // Calculate percentages
-        let total_pixels = rgb_pixels.len() as f32;
-        let percentages: Vec<f32> = kmeans_result
-            .indices
-            .iter()
-            .fold(vec![0; k], |mut acc, &cluster_index| {
-                if (cluster_index as usize) < k {
-                    acc[cluster_index as usize] += 1;
-                }
-                acc
-            })
+        let (colors, indices) = match self.color_space {
+            ColorSpace::Rgb => {
+                // Perform k-means clustering in raw sRGB space
+                let kmeans_result = run_kmeans(k, &rgb_pixels, weights.as_deref(), &kmeans_config);
+
+                let colors: Vec<Color> = kmeans_result
+                    .centroids
+                    .iter()
+                    .map(|centroid| Color::new(centroid.r, centroid.g, centroid.b))
+                    .collect();
+
+                (colors, kmeans_result.indices)
+            }
+            ColorSpace::Lab => {
+                // Convert to CIELAB and cluster using ΔE2000 as the centroid distance
+                let lab_pixels: Vec<Lab> = rgb_pixels
+                    .iter()
+                    .map(|rgb| rgb_to_lab(rgb.r, rgb.g, rgb.b))
+                    .collect();
+
+                let kmeans_result = run_kmeans(k, &lab_pixels, weights.as_deref(), &kmeans_config);
+
+                let colors: Vec<Color> = kmeans_result
+                    .centroids
+                    .iter()
+                    .map(|centroid| {
+                        let (r, g, b) = lab_to_rgb(centroid);
+                        Color::new(r, g, b)
+                    })
+                    .collect();
+
+                (colors, kmeans_result.indices)
+            }
+        };
+
+        // Calculate percentages, weighted by the same per-pixel factor used for clustering
+        let mut tallies = vec![0.0f32; k];
+        let mut total_weight = 0.0f32;
+        for (i, &cluster_index) in indices.iter().enumerate() {
+            let weight = weights.as_ref().map_or(1.0, |w| w[i]);
+            if (cluster_index as usize) < k {
+                tallies[cluster_index as usize] += weight;
+            }
+            total_weight += weight;
+        }
+        let percentages: Vec<f32> = tallies
             .iter()
-            .map(|&count| (count as f32 / total_pixels) * 100.0)
+            .map(|&tally| (tally / total_weight) * 100.0)
             .collect();
 
         Ok(PaletteResult { colors, percentages })
@@ -308,6 +944,70 @@ impl PaletteExtractor {
         let result = self.extract_palette_from_pixels(pixels, 1)?;
         result.get_color(0).ok_or_else(|| JsValue::from_str("Failed to extract dominant color"))
     }
+
+    /// Fast, deterministic alternative to k-means: quantizes pixels into a 3D histogram
+    /// and returns the `k` most occupied buckets instead of clustering.
+    #[wasm_bindgen]
+    pub fn extract_palette_histogram(
+        &self,
+        pixels: &[u8],
+        bits_per_channel: u8,
+        k: usize,
+    ) -> Result<PaletteResult, JsValue> {
+        if !pixels.len().is_multiple_of(4) {
+            return Err(JsValue::from_str("Pixel data must be RGBA format (length divisible by 4)"));
+        }
+
+        if bits_per_channel == 0 || bits_per_channel > 8 {
+            return Err(JsValue::from_str("bits_per_channel must be between 1 and 8"));
+        }
+
+        if k == 0 {
+            return Err(JsValue::from_str("Number of colors (k) must be greater than 0"));
+        }
+
+        let mut bucket_counts: HashMap<u32, u32> = HashMap::new();
+        let mut total_pixels = 0u32;
+
+        for rgba in pixels.chunks_exact(4) {
+            let key = quantize_key(rgba[0], rgba[1], rgba[2], bits_per_channel);
+            *bucket_counts.entry(key).or_insert(0) += 1;
+            total_pixels += 1;
+        }
+
+        if total_pixels == 0 {
+            return Err(JsValue::from_str("No valid pixels found"));
+        }
+
+        if self.verbose {
+            console_log!(
+                "Histogram quantization found {} occupied buckets from {} pixels",
+                bucket_counts.len(),
+                total_pixels
+            );
+        }
+
+        let mut buckets: Vec<(u32, u32)> = bucket_counts.into_iter().collect();
+        // HashMap iteration order is nondeterministic, so break ties on bucket key as well —
+        // otherwise which buckets survive `truncate` below could vary run to run.
+        buckets.sort_by_key(|&(key, count)| (std::cmp::Reverse(count), key));
+        buckets.truncate(k);
+
+        let colors: Vec<Color> = buckets
+            .iter()
+            .map(|(key, _)| {
+                let (r, g, b) = bucket_to_rgb(*key, bits_per_channel);
+                Color::new(r, g, b)
+            })
+            .collect();
+
+        let percentages: Vec<f32> = buckets
+            .iter()
+            .map(|(_, count)| (*count as f32 / total_pixels as f32) * 100.0)
+            .collect();
+
+        Ok(PaletteResult { colors, percentages })
+    }
 }
 
 // Utility functions
@@ -329,6 +1029,34 @@ pub fn sort_colors_by_luminance(colors: Vec<Color>) -> Vec<Color> {
     color_luminance.into_iter().map(|(color, _)| color).collect()
 }
 
+#[wasm_bindgen]
+pub fn sort_colors_by_hue(colors: Vec<Color>) -> Vec<Color> {
+    let mut color_hue: Vec<(Color, f32)> = colors
+        .into_iter()
+        .map(|color| {
+            let (h, _, _) = rgb_to_hsl(color.r, color.g, color.b);
+            (color, h)
+        })
+        .collect();
+
+    color_hue.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+    color_hue.into_iter().map(|(color, _)| color).collect()
+}
+
+#[wasm_bindgen]
+pub fn sort_colors_by_saturation(colors: Vec<Color>) -> Vec<Color> {
+    let mut color_saturation: Vec<(Color, f32)> = colors
+        .into_iter()
+        .map(|color| {
+            let (_, s, _) = rgb_to_hsl(color.r, color.g, color.b);
+            (color, s)
+        })
+        .collect();
+
+    color_saturation.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+    color_saturation.into_iter().map(|(color, _)| color).collect()
+}
+
 #[wasm_bindgen]
 pub fn color_distance_rgb(color1: &Color, color2: &Color) -> f32 {
     let dr = (color1.r as f32 - color2.r as f32).powi(2);
@@ -337,6 +1065,204 @@ pub fn color_distance_rgb(color1: &Color, color2: &Color) -> f32 {
     (dr + dg + db).sqrt()
 }
 
+// Bits per channel used to bucket palettes onto a shared coarse grid for similarity scoring
+const SIMILARITY_BITS_PER_CHANNEL: u8 = 4;
+
+fn palette_histogram_vector(result: &PaletteResult) -> HashMap<u32, f32> {
+    let mut vector: HashMap<u32, f32> = HashMap::new();
+    for (color, &percentage) in result.colors.iter().zip(result.percentages.iter()) {
+        let key = quantize_key(color.r, color.g, color.b, SIMILARITY_BITS_PER_CHANNEL);
+        *vector.entry(key).or_insert(0.0) += percentage;
+    }
+    vector
+}
+
+/// Cosine similarity between two palettes' normalized counts over a shared coarse histogram
+/// grid. Returns 0 if either palette is empty.
+#[wasm_bindgen]
+pub fn palette_similarity(a: &PaletteResult, b: &PaletteResult) -> f32 {
+    if a.colors.is_empty() || b.colors.is_empty() {
+        return 0.0;
+    }
+
+    let vector_a = palette_histogram_vector(a);
+    let vector_b = palette_histogram_vector(b);
+
+    let dot: f32 = vector_a
+        .iter()
+        .filter_map(|(key, value_a)| vector_b.get(key).map(|value_b| value_a * value_b))
+        .sum();
+
+    let norm_a = vector_a.values().map(|v| v * v).sum::<f32>().sqrt();
+    let norm_b = vector_b.values().map(|v| v * v).sum::<f32>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+// Standard CSS3/X11 named colors, compiled in so name lookup needs no network access.
+static NAMED_COLORS: &[(&str, u8, u8, u8)] = &[
+    ("black", 0, 0, 0),
+    ("white", 255, 255, 255),
+    ("red", 255, 0, 0),
+    ("lime", 0, 255, 0),
+    ("blue", 0, 0, 255),
+    ("yellow", 255, 255, 0),
+    ("cyan", 0, 255, 255),
+    ("magenta", 255, 0, 255),
+    ("silver", 192, 192, 192),
+    ("gray", 128, 128, 128),
+    ("maroon", 128, 0, 0),
+    ("olive", 128, 128, 0),
+    ("green", 0, 128, 0),
+    ("purple", 128, 0, 128),
+    ("teal", 0, 128, 128),
+    ("navy", 0, 0, 128),
+    ("orange", 255, 165, 0),
+    ("gold", 255, 215, 0),
+    ("pink", 255, 192, 203),
+    ("hotpink", 255, 105, 180),
+    ("crimson", 220, 20, 60),
+    ("indianred", 205, 92, 92),
+    ("firebrick", 178, 34, 34),
+    ("darkred", 139, 0, 0),
+    ("salmon", 250, 128, 114),
+    ("coral", 255, 127, 80),
+    ("tomato", 255, 99, 71),
+    ("orangered", 255, 69, 0),
+    ("chocolate", 210, 105, 30),
+    ("sienna", 160, 82, 45),
+    ("brown", 165, 42, 42),
+    ("peru", 205, 133, 63),
+    ("tan", 210, 180, 140),
+    ("khaki", 240, 230, 140),
+    ("darkkhaki", 189, 183, 107),
+    ("wheat", 245, 222, 179),
+    ("beige", 245, 245, 220),
+    ("ivory", 255, 255, 240),
+    ("lavender", 230, 230, 250),
+    ("plum", 221, 160, 221),
+    ("violet", 238, 130, 238),
+    ("orchid", 218, 112, 214),
+    ("fuchsia", 255, 0, 255),
+    ("mediumpurple", 147, 112, 219),
+    ("darkviolet", 148, 0, 211),
+    ("indigo", 75, 0, 130),
+    ("slateblue", 106, 90, 205),
+    ("royalblue", 65, 105, 225),
+    ("steelblue", 70, 130, 180),
+    ("skyblue", 135, 206, 235),
+    ("lightblue", 173, 216, 230),
+    ("powderblue", 176, 224, 230),
+    ("turquoise", 64, 224, 208),
+    ("aquamarine", 127, 255, 212),
+    ("mediumseagreen", 60, 179, 113),
+    ("seagreen", 46, 139, 87),
+    ("forestgreen", 34, 139, 34),
+    ("darkgreen", 0, 100, 0),
+    ("olivedrab", 107, 142, 35),
+    ("yellowgreen", 154, 205, 50),
+    ("greenyellow", 173, 255, 47),
+    ("chartreuse", 127, 255, 0),
+    ("springgreen", 0, 255, 127),
+    ("lightgreen", 144, 238, 144),
+    ("palegreen", 152, 251, 152),
+    ("mintcream", 245, 255, 250),
+    ("honeydew", 240, 255, 240),
+    ("azure", 240, 255, 255),
+    ("aliceblue", 240, 248, 255),
+    ("ghostwhite", 248, 248, 255),
+    ("whitesmoke", 245, 245, 245),
+    ("gainsboro", 220, 220, 220),
+    ("lightgray", 211, 211, 211),
+    ("darkgray", 169, 169, 169),
+    ("dimgray", 105, 105, 105),
+    ("slategray", 112, 128, 144),
+    ("lightslategray", 119, 136, 153),
+    ("darkslategray", 47, 79, 79),
+    ("midnightblue", 25, 25, 112),
+    ("darkblue", 0, 0, 139),
+    ("mediumblue", 0, 0, 205),
+    ("cornflowerblue", 100, 149, 237),
+    ("dodgerblue", 30, 144, 255),
+    ("deepskyblue", 0, 191, 255),
+    ("cadetblue", 95, 158, 160),
+    ("darkcyan", 0, 139, 139),
+    ("darkturquoise", 0, 206, 209),
+    ("lightseagreen", 32, 178, 170),
+    ("darkslateblue", 72, 61, 139),
+    ("blueviolet", 138, 43, 226),
+    ("darkorchid", 153, 50, 204),
+    ("mediumorchid", 186, 85, 211),
+    ("darkmagenta", 139, 0, 139),
+    ("mediumvioletred", 199, 21, 133),
+    ("palevioletred", 219, 112, 147),
+    ("deeppink", 255, 20, 147),
+    ("lightpink", 255, 182, 193),
+    ("lightcoral", 240, 128, 128),
+    ("rosybrown", 188, 143, 143),
+    ("saddlebrown", 139, 69, 19),
+    ("sandybrown", 244, 164, 96),
+    ("burlywood", 222, 184, 135),
+    ("goldenrod", 218, 165, 32),
+    ("darkgoldenrod", 184, 134, 11),
+    ("darkorange", 255, 140, 0),
+    ("lightsalmon", 255, 160, 122),
+    ("lightyellow", 255, 255, 224),
+    ("lemonchiffon", 255, 250, 205),
+    ("lightgoldenrodyellow", 250, 250, 210),
+    ("papayawhip", 255, 239, 213),
+    ("moccasin", 255, 228, 181),
+    ("peachpuff", 255, 218, 185),
+    ("mistyrose", 255, 228, 225),
+    ("lavenderblush", 255, 240, 245),
+    ("seashell", 255, 245, 238),
+    ("linen", 250, 240, 230),
+    ("oldlace", 253, 245, 230),
+    ("snow", 255, 250, 250),
+    ("cornsilk", 255, 248, 220),
+    ("bisque", 255, 228, 196),
+    ("blanchedalmond", 255, 235, 205),
+    ("navajowhite", 255, 222, 173),
+    ("antiquewhite", 250, 235, 215),
+    ("floralwhite", 255, 250, 240),
+];
+
+// A nearest-match farther than this (in raw RGB distance) is considered off-palette
+const DEFAULT_NAME_DISTANCE_THRESHOLD: f32 = 60.0;
+
+/// Nearest named CSS/X11 color for `color`, falling back to its hex string if the closest
+/// named color is farther than `threshold` away.
+#[wasm_bindgen]
+pub fn name_color_with_threshold(color: &Color, threshold: f32) -> String {
+    let mut closest_name = "black";
+    let mut closest_distance = f32::MAX;
+
+    for &(name, r, g, b) in NAMED_COLORS {
+        let candidate = Color::new(r, g, b);
+        let distance = color_distance_rgb(color, &candidate);
+        if distance < closest_distance {
+            closest_distance = distance;
+            closest_name = name;
+        }
+    }
+
+    if closest_distance <= threshold {
+        closest_name.to_string()
+    } else {
+        color.to_hex()
+    }
+}
+
+/// Nearest named CSS/X11 color for `color` using the default distance threshold.
+#[wasm_bindgen]
+pub fn name_color(color: &Color) -> String {
+    name_color_with_threshold(color, DEFAULT_NAME_DISTANCE_THRESHOLD)
+}
+
 #[wasm_bindgen]
 pub fn remove_similar_colors(colors: Vec<Color>, threshold: f32) -> Vec<Color> {
     let mut result = Vec::new();
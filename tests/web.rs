@@ -116,6 +116,268 @@ fn test_remove_similar_colors() {
     assert_eq!(filtered.len(), 2, "Should filter out similar colors");
 }
 
+#[wasm_bindgen_test]
+fn test_lab_color_space_extraction() {
+    let mut extractor = PaletteExtractor::new();
+    extractor.set_color_space(ColorSpace::Lab);
+
+    // Create a simple 2x2 RGBA image with red and blue pixels
+    let pixels = vec![
+        255, 0, 0, 255,   // Red pixel
+        0, 0, 255, 255,   // Blue pixel
+        255, 0, 0, 255,   // Red pixel
+        0, 0, 255, 255,   // Blue pixel
+    ];
+
+    let result = extractor.extract_palette_from_pixels(&pixels, 2);
+    assert!(result.is_ok(), "Lab palette extraction should succeed");
+
+    let palette = result.unwrap();
+    assert_eq!(palette.length(), 2, "Should extract 2 colors");
+
+    let total_percentage: f32 = palette.percentages().iter().sum();
+    assert!((total_percentage - 100.0).abs() < 1.0, "Percentages should sum to ~100%");
+}
+
+#[wasm_bindgen_test]
+fn test_kmeans_plus_plus_reproducible() {
+    let mut extractor = PaletteExtractor::new();
+    extractor.set_init(InitMethod::KMeansPlusPlus);
+    extractor.set_seed(42);
+
+    let pixels = vec![
+        255, 0, 0, 255,   // Red
+        255, 0, 0, 255,   // Red
+        0, 0, 255, 255,   // Blue
+        0, 0, 255, 255,   // Blue
+    ];
+
+    let first = extractor.extract_palette_from_pixels(&pixels, 2).unwrap();
+    let second = extractor.extract_palette_from_pixels(&pixels, 2).unwrap();
+
+    assert_eq!(first.get_color(0).unwrap().to_hex(), second.get_color(0).unwrap().to_hex());
+    assert_eq!(first.get_color(1).unwrap().to_hex(), second.get_color(1).unwrap().to_hex());
+}
+
+#[wasm_bindgen_test]
+fn test_lightness_filter_drops_near_white_border() {
+    let mut extractor = PaletteExtractor::new();
+    extractor.set_filter_enabled(true);
+
+    // Mostly white border pixels around a handful of mid-gray pixels
+    let pixels = vec![
+        255, 255, 255, 255,
+        255, 255, 255, 255,
+        255, 255, 255, 255,
+        128, 128, 128, 255,
+    ];
+
+    let result = extractor.extract_dominant_color(&pixels).unwrap();
+    assert_eq!(result.to_hex(), "#808080", "White border pixels should be filtered out");
+}
+
+#[wasm_bindgen_test]
+fn test_lightness_filter_falls_back_when_all_filtered() {
+    let mut extractor = PaletteExtractor::new();
+    extractor.set_filter_enabled(true);
+
+    // Entirely pure white, all pixels fall outside the default lightness band
+    let pixels = vec![255, 255, 255, 255, 255, 255, 255, 255];
+
+    let result = extractor.extract_dominant_color(&pixels);
+    assert!(result.is_ok(), "Should fall back to the unfiltered set instead of erroring");
+}
+
+#[wasm_bindgen_test]
+fn test_saturation_weighting_favors_vivid_accent() {
+    // Mostly flat gray with a single vivid red pixel
+    let pixels = vec![
+        128, 128, 128, 255,
+        128, 128, 128, 255,
+        128, 128, 128, 255,
+        255, 0, 0, 255,
+    ];
+
+    let unweighted = PaletteExtractor::new();
+    let unweighted_centroid = unweighted.extract_dominant_color(&pixels).unwrap();
+
+    let mut weighted = PaletteExtractor::new();
+    weighted.set_weight(WeightMode::Saturation);
+    let weighted_centroid = weighted.extract_dominant_color(&pixels).unwrap();
+
+    let (_, unweighted_s, _) = {
+        let hsl = unweighted_centroid.to_hsl();
+        (hsl[0], hsl[1], hsl[2])
+    };
+    let (_, weighted_s, _) = {
+        let hsl = weighted_centroid.to_hsl();
+        (hsl[0], hsl[1], hsl[2])
+    };
+
+    assert!(
+        weighted_s > unweighted_s,
+        "Saturation weighting should pull the centroid toward the vivid red pixel: \
+         unweighted {} ({}) vs weighted {} ({})",
+        unweighted_centroid.to_hex(),
+        unweighted_s,
+        weighted_centroid.to_hex(),
+        weighted_s
+    );
+    assert_eq!(weighted_centroid.to_hex(), "#ff0000", "Saturation weighting should converge to the vivid pixel");
+}
+
+#[wasm_bindgen_test]
+fn test_weighted_default_init_converges_to_real_pixel_colors() {
+    // Three unambiguous, pure-color blobs with no neutral pixels to get stranded on.
+    let mut pixels = Vec::new();
+    for _ in 0..20 {
+        pixels.extend_from_slice(&[255, 0, 0, 255]);
+    }
+    for _ in 0..20 {
+        pixels.extend_from_slice(&[0, 255, 0, 255]);
+    }
+    for _ in 0..20 {
+        pixels.extend_from_slice(&[0, 0, 255, 255]);
+    }
+
+    let blobs = [Color::new(255, 0, 0), Color::new(0, 255, 0), Color::new(0, 0, 255)];
+
+    for seed in 0..30u64 {
+        // A weight mode is set but set_init is left at its default (InitMethod::Random).
+        let mut extractor = PaletteExtractor::new();
+        extractor.set_weight(WeightMode::Saturation);
+        extractor.set_seed(seed);
+
+        let result = extractor.extract_palette_from_pixels(&pixels, 3).unwrap();
+        for i in 0..3 {
+            let centroid = result.get_color(i).unwrap();
+            let min_distance = blobs
+                .iter()
+                .map(|blob| color_distance_rgb(&centroid, blob))
+                .fold(f32::MAX, f32::min);
+            assert!(
+                min_distance < 1.0,
+                "seed {}: centroid {} should land on a real blob color (min distance {})",
+                seed,
+                centroid.to_hex(),
+                min_distance
+            );
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_color_to_hsl_and_hsv() {
+    let red = Color::new(255, 0, 0);
+
+    let hsl = red.to_hsl();
+    assert!((hsl[0] - 0.0).abs() < 0.01, "Red hue should be 0 degrees");
+    assert!((hsl[1] - 1.0).abs() < 0.01, "Red saturation should be fully saturated");
+    assert!((hsl[2] - 0.5).abs() < 0.01, "Red lightness should be 0.5");
+
+    let hsv = red.to_hsv();
+    assert!((hsv[0] - 0.0).abs() < 0.01, "Red hue should be 0 degrees");
+    assert!((hsv[1] - 1.0).abs() < 0.01, "Red saturation should be fully saturated");
+    assert!((hsv[2] - 1.0).abs() < 0.01, "Red value should be 1.0");
+}
+
+#[wasm_bindgen_test]
+fn test_sort_colors_by_hue() {
+    let colors = vec![
+        Color::new(0, 0, 255),   // Blue, hue 240
+        Color::new(255, 0, 0),   // Red, hue 0
+        Color::new(0, 255, 0),   // Green, hue 120
+    ];
+
+    let sorted = sort_colors_by_hue(colors);
+    assert_eq!(sorted[0].to_hex(), "#ff0000");
+    assert_eq!(sorted[1].to_hex(), "#00ff00");
+    assert_eq!(sorted[2].to_hex(), "#0000ff");
+}
+
+#[wasm_bindgen_test]
+fn test_sort_colors_by_saturation() {
+    let colors = vec![
+        Color::new(255, 0, 0),     // Fully saturated red
+        Color::new(128, 128, 128), // Unsaturated gray
+        Color::new(200, 100, 100), // Partially saturated
+    ];
+
+    let sorted = sort_colors_by_saturation(colors);
+    assert_eq!(sorted[0].to_hex(), "#808080", "Gray should be least saturated");
+    assert_eq!(sorted[2].to_hex(), "#ff0000", "Red should be most saturated");
+}
+
+#[wasm_bindgen_test]
+fn test_extract_palette_histogram() {
+    let extractor = PaletteExtractor::new();
+
+    let pixels = vec![
+        255, 0, 0, 255,   // Red
+        255, 0, 0, 255,   // Red
+        255, 0, 0, 255,   // Red
+        0, 0, 255, 255,   // Blue
+    ];
+
+    let result = extractor.extract_palette_histogram(&pixels, 4, 2);
+    assert!(result.is_ok(), "Histogram extraction should succeed");
+
+    let palette = result.unwrap();
+    assert_eq!(palette.length(), 2, "Should return top 2 buckets");
+
+    let total_percentage: f32 = palette.percentages().iter().sum();
+    assert!((total_percentage - 100.0).abs() < 1.0, "Percentages should sum to ~100%");
+
+    // Red should dominate the first bucket since it's 3 of 4 pixels
+    assert!(palette.get_percentage(0).unwrap() > palette.get_percentage(1).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn test_palette_similarity() {
+    let extractor = PaletteExtractor::new();
+
+    let red_pixels = vec![255, 0, 0, 255, 255, 0, 0, 255, 255, 0, 0, 255, 255, 0, 0, 255];
+    let blue_pixels = vec![0, 0, 255, 255, 0, 0, 255, 255, 0, 0, 255, 255, 0, 0, 255, 255];
+
+    let red_palette = extractor.extract_palette_histogram(&red_pixels, 4, 1).unwrap();
+    let red_palette_again = extractor.extract_palette_histogram(&red_pixels, 4, 1).unwrap();
+    let blue_palette = extractor.extract_palette_histogram(&blue_pixels, 4, 1).unwrap();
+
+    let same_similarity = palette_similarity(&red_palette, &red_palette_again);
+    let different_similarity = palette_similarity(&red_palette, &blue_palette);
+
+    assert!((same_similarity - 1.0).abs() < 0.01, "Identical palettes should be maximally similar");
+    assert!(different_similarity < same_similarity, "Red and blue palettes should be less similar");
+}
+
+#[wasm_bindgen_test]
+fn test_name_color_matches_known_colors() {
+    assert_eq!(name_color(&Color::new(255, 0, 0)), "red");
+    assert_eq!(name_color(&Color::new(0, 0, 0)), "black");
+    assert_eq!(name_color(&Color::new(255, 255, 255)), "white");
+}
+
+#[wasm_bindgen_test]
+fn test_name_color_falls_back_to_hex_when_off_palette() {
+    let off_palette = Color::new(73, 201, 142); // Nearest named color (mediumseagreen) is ~38.6 away
+    let name = name_color_with_threshold(&off_palette, 5.0);
+    assert_eq!(name, off_palette.to_hex(), "Should fall back to hex with a tight threshold");
+}
+
+#[wasm_bindgen_test]
+fn test_palette_result_names() {
+    let extractor = PaletteExtractor::new();
+    let pixels = vec![
+        255, 0, 0, 255,   // Red
+        0, 0, 0, 255,     // Black
+    ];
+
+    let palette = extractor.extract_palette_from_pixels(&pixels, 2).unwrap();
+    let names = palette.names();
+    assert_eq!(names.len(), 2);
+    assert!(names.contains(&"red".to_string()) || names.contains(&"black".to_string()));
+}
+
 #[wasm_bindgen_test]
 fn test_invalid_input_handling() {
     let extractor = PaletteExtractor::new();